@@ -1,14 +1,24 @@
+use std::collections::{HashSet, VecDeque};
 use cosmwasm_std::{
-    entry_point, from_binary, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    QueryResponse, Response, StdError, StdResult, Uint128, WasmMsg,
+    entry_point, from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    QueryResponse, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
+use schemars::JsonSchema;
 use secret_toolkit::snip20;
+use secret_toolkit::storage::Item;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use crate::msg::{
-    CurrentRoundResponse, ExecuteMsg, HasClaimedResponse,
-    InstantiateMsg, QueryMsg, ReceiveMsg, SendMsg,
+    BridgeMsg, ConfigResponse, CurrentRoundResponse, ExecuteMsg, HasClaimedResponse,
+    InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg, SendMsg,
 };
-use crate::state::{AirdropRound, Config, State, CLAIMS, CONFIG, STATE, CURRENT_ROUND};
+use crate::state::{
+    AirdropRound, Config, State, CLAIMS, CONFIG, CONTRACT_VERSION, STATE, CURRENT_ROUND,
+};
+
+/// On-chain schema version. Bump this and add a migration step in `migrate` whenever a
+/// stored struct's shape changes.
+const SCHEMA_VERSION: &str = "3";
 
 /// Verify merkle proof using SHA256 and sorted pair hashing
 fn verify_merkle_proof(
@@ -38,6 +48,57 @@ fn verify_merkle_proof(
     Ok(computed_root == root)
 }
 
+/// Verify an OpenZeppelin-style compressed multiproof for a batch of leaves against `root`.
+///
+/// `leaf_hashes` are the hashes of the claims being proven, in the order they were packed
+/// by the backend. `proof_flags[i]` tells us, for the i-th pairwise hash, whether its second
+/// operand comes from the running queue (`true`) or is the next element of `proof` (`false`).
+fn verify_multiproof(
+    leaf_hashes: Vec<Vec<u8>>,
+    proof: &[String],
+    proof_flags: &[bool],
+    root: &str,
+) -> StdResult<bool> {
+    let mut queue: VecDeque<Vec<u8>> = leaf_hashes.into_iter().collect();
+    let mut proof_pos = 0usize;
+
+    for &take_from_queue in proof_flags {
+        let a = queue
+            .pop_front()
+            .ok_or_else(|| StdError::generic_err("Invalid multiproof: queue exhausted"))?;
+        let b = if take_from_queue {
+            queue
+                .pop_front()
+                .ok_or_else(|| StdError::generic_err("Invalid multiproof: queue exhausted"))?
+        } else {
+            let proof_element = proof
+                .get(proof_pos)
+                .ok_or_else(|| StdError::generic_err("Invalid multiproof: proof exhausted"))?;
+            proof_pos += 1;
+            hex_to_bytes(proof_element)?
+        };
+
+        // Sorted pair hashing: sort before concatenating
+        let combined = if a <= b { [a, b].concat() } else { [b, a].concat() };
+        let mut hasher = Sha256::new();
+        hasher.update(&combined);
+        queue.push_back(hasher.finalize().to_vec());
+    }
+
+    if proof_pos != proof.len() {
+        return Err(StdError::generic_err("Invalid multiproof: unused proof elements"));
+    }
+
+    let computed_root = queue
+        .pop_front()
+        .ok_or_else(|| StdError::generic_err("Invalid multiproof: no leaves"))?;
+    if !queue.is_empty() {
+        return Err(StdError::generic_err("Invalid multiproof: leftover hashes"));
+    }
+
+    Ok(format!("0x{}", hex::encode(computed_root)) == root)
+}
+
 /// Convert hex string (with or without 0x prefix) to bytes
 fn hex_to_bytes(hex_str: &str) -> Result<Vec<u8>, StdError> {
     let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
@@ -71,8 +132,15 @@ pub fn instantiate(
         erth_token_hash: msg.erth_token_hash.clone(),
         allocation_contract,
         allocation_hash: msg.allocation_hash.clone(),
+        authorized_signers: None,
+        signature_threshold: 0,
+        bridge_contract: None,
+        bridge_hash: None,
+        max_claim_per_address: None,
+        erth_decimals: 6,
     };
     CONFIG.save(deps.storage, &config)?;
+    CONTRACT_VERSION.save(deps.storage, &SCHEMA_VERSION.to_string())?;
 
     let state = State {
         pending_reward: Uint128::zero(),
@@ -93,7 +161,15 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> StdResult<Response> {
     match msg {
-        ExecuteMsg::Claim { amount, proof } => execute_claim(deps, env, info, amount, proof),
+        ExecuteMsg::Claim { amount, proof, target_chain, recipient } => {
+            execute_claim(deps, env, info, amount, proof, target_chain, recipient)
+        }
+        ExecuteMsg::ClaimSigned { amount, signatures } => {
+            execute_claim_signed(deps, env, info, amount, signatures)
+        }
+        ExecuteMsg::ClaimBatch { claims, proof, proof_flags } => {
+            execute_claim_batch(deps, env, info, claims, proof, proof_flags)
+        }
         ExecuteMsg::ResetAirdrop { merkle_root, total_stake } => {
             execute_reset_airdrop(deps, env, info, merkle_root, total_stake)
         }
@@ -145,12 +221,35 @@ fn receive_allocation(
         .add_attribute("amount", amount.to_string()))
 }
 
+/// Clamp `claim_amount` to `config.max_claim_per_address`, if one is configured. The cap is
+/// specified in whole ERTH tokens, so it's scaled by `erth_decimals` to compare against base
+/// units; `checked_pow`/`checked_mul` turn an operator misconfiguration (e.g. `erth_decimals`
+/// large enough to overflow `10^erth_decimals`, or a cap that overflows once scaled) into a
+/// clean error instead of a panic on the claim hot path.
+fn apply_claim_cap(config: &Config, claim_amount: Uint128) -> StdResult<Uint128> {
+    let max_claim_per_address = match config.max_claim_per_address {
+        Some(max_claim_per_address) => max_claim_per_address,
+        None => return Ok(claim_amount),
+    };
+
+    let scale = 10u128
+        .checked_pow(config.erth_decimals as u32)
+        .ok_or_else(|| StdError::generic_err("erth_decimals is too large"))?;
+    let effective_cap = max_claim_per_address
+        .checked_mul(Uint128::from(scale))
+        .map_err(|_| StdError::generic_err("max_claim_per_address overflowed while scaling"))?;
+
+    Ok(claim_amount.min(effective_cap))
+}
+
 fn execute_claim(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     stake_amount: Uint128,
     proof: Vec<String>,
+    target_chain: Option<u16>,
+    recipient: Option<Binary>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let mut round = CURRENT_ROUND.load(deps.storage)
@@ -170,9 +269,15 @@ fn execute_claim(
     }
 
     // Calculate proportional claim amount: (user_stake / total_stake) * total_amount
-    let claim_amount = round.total_amount
+    let mut claim_amount = round.total_amount
         .multiply_ratio(stake_amount, round.total_stake);
 
+    // Clamp to the per-address cap, if configured. The clamped-away remainder is never added
+    // to `round.claimed_amount`, so it's already counted as unclaimed and rolls into the next
+    // round via `execute_reset_airdrop`'s `total_amount - claimed_amount` rollover — crediting
+    // it to `pending_reward` here too would double-count it.
+    claim_amount = apply_claim_cap(&config, claim_amount)?;
+
     // Mark as claimed for this round
     CLAIMS.insert(deps.storage, &(round.round_id, info.sender.clone()), &stake_amount.to_string())?;
 
@@ -180,16 +285,48 @@ fn execute_claim(
     round.claimed_amount += claim_amount;
     CURRENT_ROUND.save(deps.storage, &round)?;
 
-    // Send SNIP-20 transfer
-    let send_msg = snip20::transfer_msg(
-        info.sender.to_string(),
-        claim_amount,
-        None,
-        None,
-        256,
-        config.erth_token_hash.clone(),
-        config.erth_token_contract.to_string(),
-    )?;
+    // Pay out locally via SNIP-20 transfer, or forward to the bridge contract when the
+    // claimant asked to receive the airdrop on another chain.
+    let payout_msg = if let Some(target_chain) = target_chain {
+        let recipient = recipient.clone().ok_or_else(|| {
+            StdError::generic_err("recipient is required when target_chain is set")
+        })?;
+        let bridge_contract = config.bridge_contract.clone().ok_or_else(|| {
+            StdError::generic_err("Cross-chain claims are not enabled")
+        })?;
+        let bridge_hash = config.bridge_hash.clone().ok_or_else(|| {
+            StdError::generic_err("Cross-chain claims are not enabled")
+        })?;
+
+        // Actually deliver the tokens to the bridge contract (a bare WasmMsg::Execute moves
+        // no funds): SNIP-20 Send transfers `claim_amount` to the bridge and invokes its
+        // Receive hook with the SendToChain payload attached.
+        snip20::send_msg(
+            bridge_contract.to_string(),
+            Some(bridge_hash),
+            claim_amount,
+            Some(to_binary(&BridgeMsg::SendToChain {
+                target_chain,
+                recipient,
+                amount: claim_amount,
+            })?),
+            None,
+            None,
+            256,
+            config.erth_token_hash.clone(),
+            config.erth_token_contract.to_string(),
+        )?
+    } else {
+        snip20::transfer_msg(
+            info.sender.to_string(),
+            claim_amount,
+            None,
+            None,
+            256,
+            config.erth_token_hash.clone(),
+            config.erth_token_contract.to_string(),
+        )?
+    };
 
     // Claim allocation from allocation contract
     let allocation_claim_msg = CosmosMsg::Wasm(WasmMsg::Execute {
@@ -202,14 +339,169 @@ fn execute_claim(
     });
 
     Ok(Response::new()
-        .add_message(send_msg)
+        .add_message(payout_msg)
         .add_message(allocation_claim_msg)
         .add_attribute("action", "claim")
         .add_attribute("address", info.sender.to_string())
         .add_attribute("claim_amount", claim_amount.to_string())
+        .add_attribute("round_id", round.round_id.to_string())
+        .add_attribute("target_chain", target_chain.map(|c| c.to_string()).unwrap_or_default())
+        .add_attribute("recipient", recipient.map(|r| r.to_string()).unwrap_or_default()))
+}
+
+/// Settle many claims in one message using a single compressed merkle multiproof, so a
+/// relayer doesn't need one transaction per address. Claims already redeemed this round are
+/// skipped rather than failing the whole batch.
+fn execute_claim_batch(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    claims: Vec<(String, Uint128)>,
+    proof: Vec<String>,
+    proof_flags: Vec<bool>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut round = CURRENT_ROUND.load(deps.storage)
+        .map_err(|_| StdError::generic_err("No active airdrop round"))?;
+
+    let leaf_hashes = claims
+        .iter()
+        .map(|(address, stake_amount)| hex_to_bytes(&compute_leaf_hash(address, &stake_amount.to_string())))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    if !verify_multiproof(leaf_hashes, &proof, &proof_flags, &round.merkle_root)? {
+        return Err(StdError::generic_err("Invalid merkle multiproof"));
+    }
+
+    let mut messages = Vec::new();
+    let mut claimed_amount = Uint128::zero();
+    let mut claims_settled = 0u64;
+
+    for (address, stake_amount) in claims {
+        let addr = deps.api.addr_validate(&address)?;
+
+        // Already redeemed this round: skip rather than failing the whole batch.
+        if CLAIMS.get(deps.storage, &(round.round_id, addr.clone())).is_some() {
+            continue;
+        }
+        CLAIMS.insert(deps.storage, &(round.round_id, addr.clone()), &stake_amount.to_string())?;
+
+        // The leaves encode stake, same as `execute_claim`: pay out the proportional share,
+        // not the raw stake value, clamped to the same per-address cap so a batch can't be
+        // used to bypass it.
+        let claim_amount = round.total_amount.multiply_ratio(stake_amount, round.total_stake);
+        let claim_amount = apply_claim_cap(&config, claim_amount)?;
+
+        claimed_amount += claim_amount;
+        claims_settled += 1;
+        messages.push(snip20::transfer_msg(
+            addr.to_string(),
+            claim_amount,
+            None,
+            None,
+            256,
+            config.erth_token_hash.clone(),
+            config.erth_token_contract.to_string(),
+        )?);
+    }
+
+    round.claimed_amount += claimed_amount;
+    CURRENT_ROUND.save(deps.storage, &round)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "claim_batch")
+        .add_attribute("claims_settled", claims_settled.to_string())
+        .add_attribute("claimed_amount", claimed_amount.to_string())
         .add_attribute("round_id", round.round_id.to_string()))
 }
 
+/// Claim airdrop tokens authorized by a quorum of backend signers instead of a merkle proof.
+///
+/// The digest signed off-chain is `SHA256(round_id || canonical_address || amount)`. Each
+/// signature is checked against every authorized key; distinct matching keys are counted and
+/// must reach `config.signature_threshold` before the claim is paid out.
+fn execute_claim_signed(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    signatures: Vec<Binary>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut round = CURRENT_ROUND.load(deps.storage)
+        .map_err(|_| StdError::generic_err("No active airdrop round"))?;
+
+    // Check if already claimed in this round
+    if CLAIMS.get(deps.storage, &(round.round_id, info.sender.clone())).is_some() {
+        return Err(StdError::generic_err("Already claimed for this round"));
+    }
+
+    let authorized_signers = config.authorized_signers.clone().unwrap_or_default();
+    if authorized_signers.is_empty() || config.signature_threshold == 0 {
+        return Err(StdError::generic_err("Signed claims are not enabled"));
+    }
+
+    // Digest: SHA256(round_id || canonical_address || amount)
+    let canonical_address = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut hasher = Sha256::new();
+    hasher.update(round.round_id.to_be_bytes());
+    hasher.update(canonical_address.as_slice());
+    hasher.update(amount.u128().to_be_bytes());
+    let digest = hasher.finalize();
+
+    // Collect the set of distinct authorized keys that match a provided signature
+    let mut matched_signers: HashSet<usize> = HashSet::new();
+    for signature in &signatures {
+        for (idx, pubkey) in authorized_signers.iter().enumerate() {
+            if matched_signers.contains(&idx) {
+                continue;
+            }
+            if deps
+                .api
+                .secp256k1_verify(&digest, signature.as_slice(), pubkey.as_slice())
+                .unwrap_or(false)
+            {
+                matched_signers.insert(idx);
+                break;
+            }
+        }
+    }
+
+    if (matched_signers.len() as u8) < config.signature_threshold {
+        return Err(StdError::generic_err("Insufficient signer quorum"));
+    }
+
+    // Mark as claimed for this round
+    CLAIMS.insert(deps.storage, &(round.round_id, info.sender.clone()), &amount.to_string())?;
+
+    // Clamp the signed amount to the same per-address cap as the merkle claim paths
+    let amount = apply_claim_cap(&config, amount)?;
+
+    // Update claimed amount
+    round.claimed_amount += amount;
+    CURRENT_ROUND.save(deps.storage, &round)?;
+
+    // Send SNIP-20 transfer
+    let send_msg = snip20::transfer_msg(
+        info.sender.to_string(),
+        amount,
+        None,
+        None,
+        256,
+        config.erth_token_hash.clone(),
+        config.erth_token_contract.to_string(),
+    )?;
+
+    Ok(Response::new()
+        .add_message(send_msg)
+        .add_attribute("action", "claim_signed")
+        .add_attribute("address", info.sender.to_string())
+        .add_attribute("claim_amount", amount.to_string())
+        .add_attribute("round_id", round.round_id.to_string())
+        .add_attribute("signers_matched", matched_signers.len().to_string()))
+}
+
 fn execute_update_config(
     deps: DepsMut,
     _env: Env,
@@ -244,10 +536,13 @@ fn execute_reset_airdrop(
 
     let mut state = STATE.load(deps.storage)?;
 
-    // Calculate unclaimed from previous round (if exists)
+    // Calculate unclaimed from previous round (if exists). `ClaimSigned` amounts aren't bounded
+    // by `total_amount`/`total_stake` the way merkle claims are, so a quorum-authorized
+    // over-allocation can push `claimed_amount` above `total_amount`; use `saturating_sub` so
+    // that can never underflow and panic here, permanently wedging future resets.
     let unclaimed = if state.current_round_id > 0 {
         let prev_round = CURRENT_ROUND.load(deps.storage)?;
-        prev_round.total_amount - prev_round.claimed_amount
+        prev_round.total_amount.saturating_sub(prev_round.claimed_amount)
     } else {
         Uint128::zero()
     };
@@ -315,6 +610,124 @@ fn query_has_claimed(deps: Deps, address: String) -> StdResult<HasClaimedRespons
     })
 }
 
-fn query_config(deps: Deps) -> StdResult<Config> {
-    CONFIG.load(deps.storage)
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        erth_token_contract: config.erth_token_contract.to_string(),
+        erth_token_hash: config.erth_token_hash,
+        max_claim_per_address: config.max_claim_per_address,
+    })
+}
+
+/// Shape of `Config` before `ClaimSigned` and cross-chain delivery added their fields.
+/// Stored under the same key as `CONFIG` so `migrate_v1_to_v2` can decode old blobs.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+struct ConfigV1 {
+    owner: Addr,
+    erth_token_contract: Addr,
+    erth_token_hash: String,
+    allocation_contract: Addr,
+    allocation_hash: String,
+}
+
+const CONFIG_V1: Item<ConfigV1> = Item::new(b"config");
+
+/// Shape of `Config` after `ClaimSigned`/bridge delivery but before the per-address claim cap.
+/// Stored under the same key as `CONFIG` so `migrate_v2_to_v3` can decode v2 blobs.
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+struct ConfigV2 {
+    owner: Addr,
+    erth_token_contract: Addr,
+    erth_token_hash: String,
+    allocation_contract: Addr,
+    allocation_hash: String,
+    authorized_signers: Option<Vec<Binary>>,
+    signature_threshold: u8,
+    bridge_contract: Option<Addr>,
+    bridge_hash: Option<String>,
+}
+
+const CONFIG_V2: Item<ConfigV2> = Item::new(b"config");
+
+/// v1 -> v2: `Config` gained `authorized_signers`/`signature_threshold` (signer-quorum claims)
+/// and `bridge_contract`/`bridge_hash` (cross-chain claim delivery). Idempotent: a no-op if
+/// `Config` is already past the v1 shape.
+fn migrate_v1_to_v2(storage: &mut dyn Storage) -> StdResult<()> {
+    if CONFIG_V2.load(storage).is_ok() {
+        return Ok(());
+    }
+
+    let old = CONFIG_V1.load(storage)?;
+    let config = ConfigV2 {
+        owner: old.owner,
+        erth_token_contract: old.erth_token_contract,
+        erth_token_hash: old.erth_token_hash,
+        allocation_contract: old.allocation_contract,
+        allocation_hash: old.allocation_hash,
+        authorized_signers: None,
+        signature_threshold: 0,
+        bridge_contract: None,
+        bridge_hash: None,
+    };
+    CONFIG_V2.save(storage, &config)
+}
+
+/// v2 -> v3: `Config` gained `max_claim_per_address`/`erth_decimals` (per-address claim cap).
+/// Idempotent: a no-op if `Config` is already in the v3 shape.
+fn migrate_v2_to_v3(storage: &mut dyn Storage) -> StdResult<()> {
+    if CONFIG.load(storage).is_ok() {
+        return Ok(());
+    }
+
+    let old = CONFIG_V2.load(storage)?;
+    let config = Config {
+        owner: old.owner,
+        erth_token_contract: old.erth_token_contract,
+        erth_token_hash: old.erth_token_hash,
+        allocation_contract: old.allocation_contract,
+        allocation_hash: old.allocation_hash,
+        authorized_signers: old.authorized_signers,
+        signature_threshold: old.signature_threshold,
+        bridge_contract: old.bridge_contract,
+        bridge_hash: old.bridge_hash,
+        max_claim_per_address: None,
+        erth_decimals: 6,
+    };
+    CONFIG.save(storage, &config)
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let stored_version = CONTRACT_VERSION
+        .load(deps.storage)
+        .unwrap_or_else(|_| "1".to_string());
+
+    let stored: u32 = stored_version
+        .parse()
+        .map_err(|_| StdError::generic_err("Invalid stored contract version"))?;
+    let current: u32 = SCHEMA_VERSION.parse().unwrap();
+
+    if stored > current {
+        return Err(StdError::generic_err(format!(
+            "Cannot downgrade contract from schema version {} to {}",
+            stored, current
+        )));
+    }
+
+    // Run migration steps in order; each one is idempotent so re-running `migrate` against
+    // an already-upgraded contract (e.g. a retry) is a no-op.
+    if stored < 2 {
+        migrate_v1_to_v2(deps.storage)?;
+    }
+    if stored < 3 {
+        migrate_v2_to_v3(deps.storage)?;
+    }
+
+    CONTRACT_VERSION.save(deps.storage, &SCHEMA_VERSION.to_string())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored_version)
+        .add_attribute("to_version", SCHEMA_VERSION))
 }
@@ -18,6 +18,25 @@ pub enum ExecuteMsg {
     Claim {
         amount: Uint128,
         proof: Vec<String>,
+        /// Wormhole-style chain id to deliver the claim to instead of paying out locally
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target_chain: Option<u16>,
+        /// Encoded recipient on `target_chain`, required when `target_chain` is set
+        #[serde(skip_serializing_if = "Option::is_none")]
+        recipient: Option<Binary>,
+    },
+    /// Claim airdrop tokens authorized by a quorum of backend signers instead of a merkle proof
+    ClaimSigned {
+        amount: Uint128,
+        signatures: Vec<Binary>,
+    },
+    /// Settle many claims in one message using a single compressed merkle multiproof.
+    /// `claims` are `(address, stake_amount)` pairs, the same stake values the merkle leaves
+    /// encode in the single-claim `Claim` path; each address is paid its proportional share.
+    ClaimBatch {
+        claims: Vec<(String, Uint128)>,
+        proof: Vec<String>,
+        proof_flags: Vec<bool>,
     },
     /// Reset airdrop with new merkle root (owner only)
     ResetAirdrop {
@@ -42,6 +61,17 @@ pub enum ReceiveMsg {
     AllocationSend { allocation_id: u32 },
 }
 
+/// Messages sent to the configured bridge contract to deliver a claim cross-chain
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeMsg {
+    SendToChain {
+        target_chain: u16,
+        recipient: Binary,
+        amount: Uint128,
+    },
+}
+
 /// Query messages
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -75,6 +105,8 @@ pub struct ConfigResponse {
     pub owner: String,
     pub erth_token_contract: String,
     pub erth_token_hash: String,
+    /// Effective per-address claim cap in whole ERTH tokens, if one is configured
+    pub max_claim_per_address: Option<Uint128>,
 }
 
 /// Migration message
@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use secret_toolkit::storage::{Item, Keymap};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Uint128};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -10,6 +10,17 @@ pub struct Config {
     pub erth_token_hash: String,
     pub allocation_contract: Addr,
     pub allocation_hash: String,
+    /// Secp256k1 public keys authorized to co-sign `ClaimSigned` requests
+    pub authorized_signers: Option<Vec<Binary>>,
+    /// Number of distinct authorized signatures required to approve a `ClaimSigned` request
+    pub signature_threshold: u8,
+    /// Bridge contract used to deliver claims to a recipient on another chain
+    pub bridge_contract: Option<Addr>,
+    pub bridge_hash: Option<String>,
+    /// Maximum a single address may claim in one round, denominated in whole ERTH tokens
+    /// (scaled by `erth_decimals`, not raw base units)
+    pub max_claim_per_address: Option<Uint128>,
+    pub erth_decimals: u8,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -33,5 +44,8 @@ pub const CONFIG: Item<Config> = Item::new(b"config");
 pub const STATE: Item<State> = Item::new(b"state");
 pub const CURRENT_ROUND: Item<AirdropRound> = Item::new(b"current_round");
 
+// Schema version of the currently stored Config/State/AirdropRound structs, checked by `migrate`
+pub const CONTRACT_VERSION: Item<String> = Item::new(b"contract_version");
+
 // Claims storage with composite key (round_id, address)
 pub const CLAIMS: Keymap<(u64, Addr), String> = Keymap::new(b"claims");